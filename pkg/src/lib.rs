@@ -243,6 +243,177 @@ macro_rules! parse_unitary_variants {
     };
 }
 
+/**
+Expands to an invocation of the `$callback` macro, with a description of every variant of the provided enum.  The invocation's argument will be prefixed by the contents of `$arg`.
+
+Unlike [`parse_unitary_variants!`](macro.parse_unitary_variants.html), this will happily accept tuple and struct variants.  Each variant is passed to `$callback` as a `(Name kind)` token tree, where `kind` is one of:
+
+- `unit`, for a variant with no payload;
+- `tuple(T0, T1, ...)`, for a tuple variant, listing the field types in order;
+- `struct{f0: T0, f1: T1, ...}`, for a struct variant, listing the field names and types.
+
+If `$arg` is of the form `{…}`, then the expansion will be parsed as one or more items.  If it is of the form `(…)`, the expansion will be parsed as an expression.
+
+See [TLBoRM: Enum Parsing](https://danielkeep.github.io/tlborm/book/blk-enum-parsing.html).
+
+## Examples
+
+```rust
+# #[macro_use(parse_variants, tlborm_util)] extern crate tlborm;
+# fn main() {
+macro_rules! kind_name {
+    ($n:ident unit) => { concat!(stringify!($n), ":unit") };
+    ($n:ident tuple($($t:ty),*)) => { concat!(stringify!($n), ":tuple") };
+    ($n:ident struct{$($f:ident : $t:ty),*}) => { concat!(stringify!($n), ":struct") };
+}
+
+macro_rules! variant_kinds {
+    ($(($n:ident $($k:tt)*))*) => {
+        concat!($(kind_name!($n $($k)*), " "),*)
+    }
+}
+
+const DESC: &'static str = parse_variants!(
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rect { w: f64, h: f64 }
+    }
+    => variant_kinds()
+);
+assert_eq!(DESC, "Point:unit Circle:tuple Rect:struct ");
+# }
+```
+*/
+#[macro_export]
+macro_rules! parse_variants {
+    (
+        enum $name:ident {$($body:tt)*} => $callback:ident $arg:tt
+    ) => {
+        tlborm_util! {
+            @parse_variants
+            enum $name {$($body)*} => $callback $arg
+        }
+    };
+}
+
+/**
+Chains a series of refutable pattern matches into a single set of irrefutable bindings, without nesting a `match` for each one by hand.
+
+Each clause has the form `($scrutinee) ~ ($pattern) else $divergent`, where `$divergent` is a block that must diverge (*e.g.* by `return`ing, `break`ing, or `panic!`king).  Clauses are separated by `;`, and the whole invocation ends with `binds $name, ...`, listing every name bound across all the clauses.  Later clauses may refer to names bound by earlier ones.
+
+Internally, this is built on top of [`as_expr!`](macro.as_expr.html) and [`as_pat!`](macro.as_pat.html), which force each scrutinee and pattern to be reparsed correctly after being passed through the `tlborm_util!` muncher.
+
+See [TLBoRM: Patterns](https://danielkeep.github.io/tlborm/book/pat-README.html).
+
+## Examples
+
+```rust
+# #[macro_use(biased_match, as_expr, as_pat, tlborm_util)] extern crate tlborm;
+# fn main() {
+fn first_even(v: &[i32]) -> Option<(usize, i32)> {
+    v.iter().enumerate().find(|&(_, &x)| x % 2 == 0).map(|(i, &x)| (i, x))
+}
+
+fn describe(v: &[i32]) -> i32 {
+    biased_match!(
+        (first_even(v)) ~ (Some((idx, val))) else { return -1 };
+        binds idx, val
+    );
+    idx as i32 + val
+}
+
+assert_eq!(describe(&[1, 3, 4, 5]), 2 + 4);
+assert_eq!(describe(&[1, 3, 5]), -1);
+# }
+```
+*/
+#[macro_export]
+macro_rules! biased_match {
+    ($($input:tt)*) => {
+        let tlborm_util!(@biased_match_names $($input)*)
+            = tlborm_util!(@biased_match_expr $($input)*);
+    };
+}
+
+/**
+Matches a flat sequence of token trees against a list of arms, the way a slice pattern would, including a trailing `..` to capture the remainder.
+
+Each arm has the form `($name0 $name1 ... $rest ..) => { ... }` or, without a rest-capture, `($name0 $name1 ...) => { ... }`, which only matches input of that exact length.  Every `$name` binds a single token tree; the trailing `$rest ..`, if present, binds every token tree left over to `$rest`, to be expanded with `$($rest)*` in the arm's body.  Arms are tried in order, and the first one whose shape fits the input wins.
+
+This is implemented by rewriting each arm's pattern into an ordinary `macro_rules!` matcher (`$name:tt` for a fixed slot, `$($rest:tt)*` for the trailing capture), then defining and immediately invoking that matcher.  It's a natural companion to the counting macros: [`count_tts_flat!`](macro.count_tts_flat.html) tells you how many token trees you have, and `tt_match!` lets you destructure them.
+
+See [TLBoRM: Repetition replacement](https://danielkeep.github.io/tlborm/book/pat-repetition-replacement.html).
+
+## Examples
+
+```rust
+# #[macro_use(tt_match, tlborm_util)] extern crate tlborm;
+# fn main() {
+let sum = tt_match!(
+    (1 2 3 4 5) {
+        ($x $y $rest ..) => {
+            $x + $y + 0 $(+ $rest)*
+        }
+    }
+);
+assert_eq!(sum, 15);
+
+let first_two = tt_match!(
+    (a b) {
+        ($x $y) => { stringify!($x $y) }
+    }
+);
+assert_eq!(first_two, "a b");
+# }
+```
+*/
+#[macro_export]
+macro_rules! tt_match {
+    (($($input:tt)*) { $($arms:tt)* }) => {
+        tlborm_util!(@tt_match ($($input)*) { $($arms)* })
+    };
+}
+
+/**
+Statically checks a `macro_rules`-style matcher fragment against Rust's fragment-follow-set rules, turning a future-compatibility footgun into a `compile_error!`.
+
+The matcher is written as plain token trees (so a literal `$` has to be typed, same as the real thing it's describing).  For every `$name:kind` it finds, `check_follow!` inspects the token tree immediately after it:
+
+- after `expr` or `stmt`, only `=>`, `,`, or `;` may follow;
+- after `ty` or `path`, only `=>`, `,`, `=`, `|`, `;`, `:`, `>`, `[`, `{`, `as`, or `where` may follow;
+- after `pat`, only `=>`, `,`, `=`, `|`, `if`, or `in` may follow;
+- `ident`, `lifetime`, `tt`, `block`, `item`, and `meta` may be followed by anything.
+
+A fragment at the very end of the matcher is always fine.  Anything else triggers a `compile_error!` naming the offending fragment and token.
+
+See [TLBoRM: Fragment Specifiers](https://danielkeep.github.io/tlborm/book/mbe-macro-rules.html#fragment-specifiers).
+
+## Examples
+
+```rust
+# #[macro_use(check_follow, tlborm_util)] extern crate tlborm;
+check_follow!($e:expr => $rest:tt);
+# fn main() {}
+```
+
+The following does *not* compile, since an `expr` fragment cannot be followed by another `expr` fragment:
+
+<!-- NO-FAILING-TESTS -->
+
+```ignore
+# #[macro_use(check_follow, tlborm_util)] extern crate tlborm;
+check_follow!($e:expr $f:expr);
+# fn main() {}
+```
+*/
+#[macro_export]
+macro_rules! check_follow {
+    ($($matcher:tt)*) => {
+        tlborm_util!(@check_follow $($matcher)*);
+    };
+}
+
 /**
 Utility macro that takes a token tree and an expression, expanding to the expression.
 
@@ -381,6 +552,298 @@ macro_rules! tlborm_util {
         @collect_unitary_variants $fixed:tt,
         ($var:ident $_struct:tt, $($tail:tt)*) -> ($($var_names:tt)*)
     ) => {
-        const _error: () = "cannot parse unitary variants from enum with non-unitary variants";
+        compile_error!(concat!(
+            "`parse_unitary_variants!` cannot parse variant `",
+            stringify!($var),
+            "`, which has a payload; see `parse_variants!` instead"
+        ));
+    };
+
+    // ========================================================================
+    // @parse_variants
+    (
+        @parse_variants
+        enum $name:ident {$($body:tt)*} => $callback:ident $arg:tt
+    ) => {
+        tlborm_util! {
+            @collect_variants
+            ($callback $arg), ($($body)*,) -> ()
+        }
+    };
+
+    // ========================================================================
+    // @collect_variants
+    // Exit rules.
+    (
+        @collect_variants ($callback:ident ( $($args:tt)* )),
+        ($(,)*) -> ($($variants:tt)*)
+    ) => {
+        tlborm_util! {
+            @as_expr
+            $callback!{ $($args)* $($variants)* }
+        }
+    };
+
+    (
+        @collect_variants ($callback:ident { $($args:tt)* }),
+        ($(,)*) -> ($($variants:tt)*)
+    ) => {
+        tlborm_util! {
+            @as_item
+            $callback!{ $($args)* $($variants)* }
+        }
+    };
+
+    // Consume an attribute.
+    (
+        @collect_variants $fixed:tt,
+        (#[$_attr:meta] $($tail:tt)*) -> ($($variants:tt)*)
+    ) => {
+        tlborm_util! {
+            @collect_variants $fixed,
+            ($($tail)*) -> ($($variants)*)
+        }
+    };
+
+    // Handle a tuple variant.
+    (
+        @collect_variants $fixed:tt,
+        ($var:ident ( $($fields:ty),* $(,)* ), $($tail:tt)*) -> ($($variants:tt)*)
+    ) => {
+        tlborm_util! {
+            @collect_variants $fixed,
+            ($($tail)*) -> ($($variants)* ($var tuple($($fields),*)))
+        }
+    };
+
+    // Handle a struct variant.
+    (
+        @collect_variants $fixed:tt,
+        ($var:ident { $($fname:ident : $fty:ty),* $(,)* }, $($tail:tt)*) -> ($($variants:tt)*)
+    ) => {
+        tlborm_util! {
+            @collect_variants $fixed,
+            ($($tail)*) -> ($($variants)* ($var struct{$($fname: $fty),*}))
+        }
+    };
+
+    // Handle a unit variant, optionally with an initialiser.
+    (
+        @collect_variants $fixed:tt,
+        ($var:ident $(= $_val:expr)*, $($tail:tt)*) -> ($($variants:tt)*)
+    ) => {
+        tlborm_util! {
+            @collect_variants $fixed,
+            ($($tail)*) -> ($($variants)* ($var unit))
+        }
+    };
+
+    // ========================================================================
+    // @biased_match_names
+    // Walks the clause list purely to find the trailing `binds` list, so the
+    // outer `let` pattern and the innermost match arm agree on what's bound.
+    (
+        @biased_match_names
+        ($_e:expr) ~ ($_p:pat) else $_err:block; $($rest:tt)*
+    ) => {
+        tlborm_util!(@biased_match_names $($rest)*)
+    };
+
+    (@biased_match_names binds $($names:ident),* $(,)*) => {
+        ($($names),*)
+    };
+
+    // ========================================================================
+    // @biased_match_expr
+    // Builds the nested `match` expression; each clause's failure arm
+    // diverges, so the happy-path bindings flow out as the expression's value.
+    (
+        @biased_match_expr
+        ($e:expr) ~ ($p:pat) else $err:block; binds $($names:ident),* $(,)*
+    ) => {
+        match tlborm_util!(@as_expr $e) {
+            tlborm_util!(@as_pat $p) => ($($names),*),
+            _ => $err,
+        }
+    };
+
+    (
+        @biased_match_expr
+        ($e:expr) ~ ($p:pat) else $err:block; $($rest:tt)*
+    ) => {
+        match tlborm_util!(@as_expr $e) {
+            tlborm_util!(@as_pat $p) => tlborm_util!(@biased_match_expr $($rest)*),
+            _ => $err,
+        }
+    };
+
+    // ========================================================================
+    // @tt_match
+    (@tt_match ($($input:tt)*) { $($arms:tt)* }) => {
+        tlborm_util! {
+            @tt_match_arms ($($input)*), ($($arms)*) -> ()
+        }
+    };
+
+    // ------------------------------------------------------------------------
+    // @tt_match_arms
+    // Consumes one user-written arm at a time, rewriting its pattern into a
+    // real `macro_rules!` matcher and appending it to the accumulator.
+
+    // No arms left: define the generated matcher and invoke it.
+    (
+        @tt_match_arms ($($input:tt)*), () -> ($($rules:tt)*)
+    ) => {
+        {
+            macro_rules! __tt_match_inner { $($rules)* }
+            __tt_match_inner!($($input)*)
+        }
+    };
+
+    // One arm left to translate.
+    (
+        @tt_match_arms
+        ($($input:tt)*),
+        ( ($($pat:tt)*) => { $($body:tt)* } $($tail:tt)* ) -> ($($rules:tt)*)
+    ) => {
+        tlborm_util! {
+            @tt_match_pat
+            (($($input)*), ($($tail)*) -> ($($rules)*), { $($body)* }),
+            ($($pat)*) -> ()
+        }
+    };
+
+    // ------------------------------------------------------------------------
+    // @tt_match_pat
+    // Rewrites `$name` into `$name:tt`, and a trailing `$name ..` into
+    // `$($name:tt)*`, then hands back to `@tt_match_arms` via `@tt_match_pat_done`.
+
+    // Trailing rest-capture.
+    (
+        @tt_match_pat $fixed:tt, ($dollar:tt $name:ident ..) -> ($($out:tt)*)
+    ) => {
+        tlborm_util! {
+            @tt_match_pat_done $fixed, ($($out)* $dollar ( $dollar $name : tt ) *)
+        }
+    };
+
+    // One fixed slot.
+    (
+        @tt_match_pat $fixed:tt, ($dollar:tt $name:ident $($tail:tt)*) -> ($($out:tt)*)
+    ) => {
+        tlborm_util! {
+            @tt_match_pat $fixed, ($($tail)*) -> ($($out)* $dollar $name : tt)
+        }
+    };
+
+    // No more pattern tokens: exact-length arm, no rest-capture.
+    (
+        @tt_match_pat $fixed:tt, () -> ($($out:tt)*)
+    ) => {
+        tlborm_util! {
+            @tt_match_pat_done $fixed, ($($out)*)
+        }
+    };
+
+    (
+        @tt_match_pat_done
+        (($($input:tt)*), ($($tail:tt)*) -> ($($rules:tt)*), { $($body:tt)* }),
+        ($($matcher:tt)*)
+    ) => {
+        tlborm_util! {
+            @tt_match_arms
+            ($($input)*), ($($tail)*) -> ($($rules)* ($($matcher)*) => { $($body)* };)
+        }
+    };
+
+    // ========================================================================
+    // @check_follow
+    (@check_follow $($matcher:tt)*) => {
+        tlborm_util!(@check_follow_scan $($matcher)*);
+    };
+
+    // Found a fragment: inspect whatever comes after it.
+    (@check_follow_scan $dollar:tt $name:ident : $kind:ident $($rest:tt)*) => {
+        tlborm_util!(@check_follow_check $kind, $name, $($rest)*);
+    };
+
+    // Not a fragment; skip a token and keep scanning.
+    (@check_follow_scan $_head:tt $($rest:tt)*) => {
+        tlborm_util!(@check_follow_scan $($rest)*);
+    };
+
+    (@check_follow_scan) => {};
+
+    // ------------------------------------------------------------------------
+    // `expr` / `stmt`: only `=>`, `,`, `;` may follow.
+    (@check_follow_check expr, $name:ident,) => {};
+    (@check_follow_check stmt, $name:ident,) => {};
+
+    (@check_follow_check expr, $name:ident, => $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check expr, $name:ident, , $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check expr, $name:ident, ; $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+
+    (@check_follow_check stmt, $name:ident, => $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check stmt, $name:ident, , $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check stmt, $name:ident, ; $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+
+    // ------------------------------------------------------------------------
+    // `ty` / `path`: `=>`, `,`, `=`, `|`, `;`, `:`, `>`, `[...]`, `{...}`, `as`, `where`.
+    (@check_follow_check ty, $name:ident,) => {};
+    (@check_follow_check path, $name:ident,) => {};
+
+    (@check_follow_check ty, $name:ident, => $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, , $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, = $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, | $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, ; $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, : $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, > $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, as $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, where $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, [$($_g:tt)*] $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check ty, $name:ident, {$($_g:tt)*} $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+
+    (@check_follow_check path, $name:ident, => $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, , $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, = $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, | $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, ; $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, : $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, > $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, as $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, where $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, [$($_g:tt)*] $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check path, $name:ident, {$($_g:tt)*} $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+
+    // ------------------------------------------------------------------------
+    // `pat`: `=>`, `,`, `=`, `|`, `if`, `in`.
+    (@check_follow_check pat, $name:ident,) => {};
+
+    (@check_follow_check pat, $name:ident, => $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check pat, $name:ident, , $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check pat, $name:ident, = $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check pat, $name:ident, | $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check pat, $name:ident, if $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check pat, $name:ident, in $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+
+    // ------------------------------------------------------------------------
+    // `ident`, `lifetime`, `tt`, `block`, `item`, `meta`: anything may follow.
+    (@check_follow_check ident, $name:ident, $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check lifetime, $name:ident, $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check tt, $name:ident, $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check block, $name:ident, $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check item, $name:ident, $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+    (@check_follow_check meta, $name:ident, $($rest:tt)*) => { tlborm_util!(@check_follow_scan $($rest)*); };
+
+    // A fragment of any other kind at the very end is fine too.
+    (@check_follow_check $kind:ident, $name:ident,) => {};
+
+    // Anything left unmatched above is a follow-set violation.
+    (@check_follow_check $kind:ident, $name:ident, $bad:tt $($rest:tt)*) => {
+        compile_error!(concat!(
+            "`$", stringify!($name), ":", stringify!($kind),
+            "` may not be followed by `", stringify!($bad), "`"
+        ));
     };
 }